@@ -1,14 +1,178 @@
-use std::io::Write;
-use sha2::Sha256;
+use std::io::{BufRead, BufReader, Write};
+use sha1::Sha1;
+use sha2::{Sha256, Sha512};
 use sha2::digest::Digest;
 use rayon::prelude::*;
 use structopt::StructOpt;
 
+/// Hash algorithm to compute over each candidate email and compare against `--hashed-emails`.
+#[derive(Clone, Copy)]
+enum HashKind {
+    Sha256,
+    Sha512,
+    Sha1,
+    Md5,
+    /// Double SHA-256, i.e. `sha256(sha256(x))` as used in Bitcoin-style hashing.
+    Sha256d,
+}
+
+impl HashKind {
+    fn hash_hex(&self, data: &[u8]) -> String {
+        match self {
+            HashKind::Sha256 => {
+                let mut hasher = Sha256::new();
+                hasher.update(data);
+                hex::encode(hasher.finalize())
+            }
+            HashKind::Sha512 => {
+                let mut hasher = Sha512::new();
+                hasher.update(data);
+                hex::encode(hasher.finalize())
+            }
+            HashKind::Sha1 => {
+                let mut hasher = Sha1::new();
+                hasher.update(data);
+                hex::encode(hasher.finalize())
+            }
+            HashKind::Md5 => format!("{:x}", md5::compute(data)),
+            HashKind::Sha256d => {
+                let mut hasher = Sha256::new();
+                hasher.update(data);
+                let first_pass = hasher.finalize();
+                let mut hasher = Sha256::new();
+                hasher.update(first_pass);
+                hex::encode(hasher.finalize())
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for HashKind {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "sha256" => Ok(HashKind::Sha256),
+            "sha512" => Ok(HashKind::Sha512),
+            "sha1" => Ok(HashKind::Sha1),
+            "md5" => Ok(HashKind::Md5),
+            "sha256d" => Ok(HashKind::Sha256d),
+            unknown => Err(format!("Unknown hash algorithm: {}", unknown)),
+        }
+    }
+}
+
+/// A single normalization rule applied to a candidate email before hashing, mirroring how
+/// identity/ad-tech datasets canonicalize emails prior to hashing them.
+#[derive(Clone, Copy, PartialEq)]
+enum NormalizeRule {
+    /// Trim surrounding whitespace from the whole address.
+    Trim,
+    /// Lowercase the whole address.
+    Lowercase,
+    /// Strip dots from the local part (Gmail treats `john.doe` and `johndoe` as the same inbox).
+    GmailDots,
+    /// Strip a `+tag` suffix from the local part.
+    PlusTags,
+}
+
+impl std::str::FromStr for NormalizeRule {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "trim" => Ok(NormalizeRule::Trim),
+            "lowercase" => Ok(NormalizeRule::Lowercase),
+            "gmail-dots" => Ok(NormalizeRule::GmailDots),
+            "plus-tags" => Ok(NormalizeRule::PlusTags),
+            unknown => Err(format!("Unknown normalization rule: {}", unknown)),
+        }
+    }
+}
+
+/// Apply whichever of the given normalization rules are present to a candidate email, always
+/// in the fixed order trim -> lowercase -> strip `+tag` -> strip dots, regardless of the order
+/// the caller listed them in.
+fn normalize_email(mut email: String, rules: &[NormalizeRule]) -> String {
+    if rules.contains(&NormalizeRule::Trim) {
+        email = email.trim().to_string();
+    }
+    if rules.contains(&NormalizeRule::Lowercase) {
+        email = email.to_lowercase();
+    }
+    if rules.contains(&NormalizeRule::GmailDots) || rules.contains(&NormalizeRule::PlusTags) {
+        if let Some(at_index) = email.find('@') {
+            let (local, domain) = email.split_at(at_index);
+            let mut local = local.to_string();
+            if rules.contains(&NormalizeRule::PlusTags) {
+                if let Some(plus_index) = local.find('+') {
+                    local.truncate(plus_index);
+                }
+            }
+            if rules.contains(&NormalizeRule::GmailDots) {
+                local = local.replace('.', "");
+            }
+            email = format!("{}{}", local, domain);
+        }
+    }
+    email
+}
+
+/// Parse a hashcat-style mask into one alphabet string per position: `?l`/`?u`/`?d`/`?s`/`?a`
+/// select the lowercase/uppercase/digit/symbol/all-of-the-above built-in classes, `[...]`
+/// gives an explicit bracketed set of characters for that position, and any other character
+/// is taken as a literal single-character position.
+fn parse_mask(mask: &str) -> Result<Vec<String>, String> {
+    const LOWER: &str = "abcdefghijklmnopqrstuvwxyz";
+    const UPPER: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    const DIGITS: &str = "0123456789";
+    const SYMBOLS: &str = "!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~";
+    let mut alphabets = Vec::new();
+    let mut chars = mask.chars();
+    while let Some(character) = chars.next() {
+        match character {
+            '?' => {
+                let token = chars.next().ok_or("Mask ends with a dangling '?'")?;
+                let alphabet = match token {
+                    'l' => LOWER.to_string(),
+                    'u' => UPPER.to_string(),
+                    'd' => DIGITS.to_string(),
+                    's' => SYMBOLS.to_string(),
+                    'a' => format!("{}{}{}{}", LOWER, UPPER, DIGITS, SYMBOLS),
+                    other => return Err(format!("Unknown mask placeholder: ?{}", other)),
+                };
+                alphabets.push(alphabet);
+            }
+            '[' => {
+                let mut set = String::new();
+                loop {
+                    match chars.next() {
+                        Some(']') => break,
+                        Some(set_char) => set.push(set_char),
+                        None => return Err("Mask has an unterminated '['".to_string()),
+                    }
+                }
+                if set.is_empty() {
+                    return Err("Mask has an empty bracketed set".to_string());
+                }
+                alphabets.push(set);
+            }
+            literal => alphabets.push(literal.to_string()),
+        }
+    }
+    if alphabets.is_empty() {
+        return Err("Mask must describe at least one position".to_string());
+    }
+    Ok(alphabets)
+}
+
 /// Strings generator.
-/// 
-/// Generate strings with combinations of the given chars as string with a maximum length.
-/// 
-/// For example, for `abc` with maximum length of 2 the generated strings will be:
+///
+/// Generate strings with combinations of per-position alphabets, up to a maximum length
+/// (the number of alphabets given).
+///
+/// For example, for the alphabet `abc` repeated twice (maximum length of 2) the generated
+/// strings will be:
 ///  - `a`
 ///  - `b`
 ///  - `c`
@@ -22,22 +186,156 @@ use structopt::StructOpt;
 ///  - `cb`
 ///  - `cc`
 struct StringsGenerator<'a> {
-    dictionary: &'a str,
+    alphabets: Vec<&'a str>,
     generators: Vec<std::str::Chars<'a>>,
     current_combination: String,
 }
 
 impl<'a> StringsGenerator<'a> {
-    fn new(max_length: usize, dictionary: &'a str) -> Self {
-        let mut generator = Self {
-            dictionary,
-            generators: Vec::new(),
+    /// `alphabets[i]` is the charset used at position `i` (0 = leftmost/most-significant).
+    /// A plain dictionary attack uses the same alphabet at every position; a `--mask` gives
+    /// each position its own.
+    fn new(alphabets: Vec<&'a str>) -> Self {
+        let max_length = alphabets.len();
+        let generators = alphabets.iter().map(|alphabet| alphabet.chars()).collect();
+        Self {
+            alphabets,
+            generators,
             current_combination: String::with_capacity(max_length),
-        };
-        while generator.generators.len() < max_length {
-            generator.generators.push(generator.dictionary.chars())
         }
-        return generator;
+    }
+
+    /// Like `new`, but skips straight to candidates of the exact length `alphabets.len()`,
+    /// instead of first running through every shorter length the way a plain dictionary
+    /// attack does.
+    ///
+    /// `new`'s shorter-length phase is built by cycling the *rightmost* positions alone
+    /// (confirmed by `seek`'s own `active_start = max_length - length`), which is harmless
+    /// when every position shares one dictionary but silently mismatches positional masks
+    /// like `--mask '?l?d'`: its length-1 candidates would be drawn only from the `?d`
+    /// alphabet, never the intended `?l` one. A mask always means "exactly this many
+    /// characters, one alphabet per position" (hashcat semantics), so this seeks straight
+    /// past every shorter-length block to the first full-length candidate.
+    /// `start` is an additional offset *within* the full-length block (e.g. a `--start` the
+    /// caller wants to resume from), not an index into the unrestricted enumeration -- seeking
+    /// with a plain `start` after this constructor would recompute from index 0 of the
+    /// shorter-length phase and discard the skip this constructor just did.
+    fn new_exact_length(alphabets: Vec<&'a str>, start: u64) -> Self {
+        let mut generator = Self::new(alphabets);
+        let max_length = generator.alphabets.len();
+        // Saturate instead of overflowing and, like `total_combinations_at_least`'s early
+        // return, stop accumulating as soon as `skip` has already blown past `u64::MAX`,
+        // since the `assert!` below will reject it either way.
+        let mut skip: u128 = 0;
+        let mut block_size: u128 = 1;
+        for length in 1..max_length {
+            block_size = block_size.saturating_mul(generator.base_at(max_length - length));
+            skip = skip.saturating_add(block_size);
+            if skip > u128::from(u64::MAX) {
+                break;
+            }
+        }
+        let skip = skip.saturating_add(u128::from(start));
+        if skip > 0 {
+            assert!(
+                skip <= u128::from(u64::MAX),
+                "mask search space is too large to index with --start/--count"
+            );
+            generator
+                .seek(skip as u64)
+                .unwrap_or_else(|error| panic!("Invalid --start: {}", error));
+        }
+        generator
+    }
+
+    #[inline]
+    fn base_at(&self, position: usize) -> u128 {
+        self.alphabets[position].chars().count() as u128
+    }
+
+    /// Total number of candidates this generator can produce (summed over all lengths
+    /// `1..=alphabets.len()`), or, if that total is larger than `at_least`, some value that is
+    /// merely guaranteed to also be `> at_least` (returned early, before accumulating further).
+    ///
+    /// `seek` only ever needs to know whether `start` is below the total, so this stops
+    /// growing the running sum as soon as that's settled. With the default 65-char dictionary
+    /// and `--max-length 64` the *exact* total overflows even `u128` (`65^64` alone dwarfs
+    /// `u128::MAX`), but realistic `start` values (bounded by `u64::MAX`) settle the
+    /// comparison within a handful of lengths, long before the sum grows that large.
+    fn total_combinations_at_least(&self, at_least: u128) -> u128 {
+        let max_length = self.alphabets.len();
+        let mut total: u128 = 0;
+        let mut block_size: u128 = 1;
+        for length in 1..=max_length {
+            block_size *= self.base_at(max_length - length);
+            total += block_size;
+            if total > at_least {
+                return total;
+            }
+        }
+        total
+    }
+
+    /// Seek so that the *next* call to `next()` yields the `start`-th candidate (0-based, in
+    /// enumeration order), without walking through every earlier candidate. Used to resume
+    /// cracking from a checkpoint or to split work across machines via `--start`/`--count`.
+    ///
+    /// This is a mixed-radix counter: there are `B, B^2, B^3, ...` strings of length
+    /// `1, 2, 3, ...` when every position shares a base `B`, or more generally the product of
+    /// the rightmost `L` positions' alphabet sizes for strings of length `L`. To recover the
+    /// state as if candidate `start - 1` had just been produced, that index is reduced by
+    /// block sizes to find its length `L` and its offset within that block, then the offset
+    /// is expanded into `L` digits (most-significant first, each digit indexing its
+    /// position's own alphabet) to recover the candidate string.
+    ///
+    /// Returns an error instead of silently reconstructing a stale candidate when `start` is
+    /// at or past the total number of candidates for this alphabet -- notably including
+    /// `start == total`, which doesn't fail the decomposition below (the reconstructed offset
+    /// `total - 1` is still a perfectly valid *last* candidate), but does mean there is no
+    /// `start`-th candidate for the caller's subsequent `next()` to produce.
+    fn seek(&mut self, start: u64) -> Result<(), String> {
+        if start == 0 {
+            // Nothing produced yet: the state from `new()` already yields candidate 0 first.
+            return Ok(());
+        }
+        let start = u128::from(start);
+        let total = self.total_combinations_at_least(start);
+        if start >= total {
+            return Err(format!(
+                "--start {} is at or past the end of the enumeration ({} total candidates for this alphabet)",
+                start, total
+            ));
+        }
+        let max_length = self.generators.len();
+        let mut offset = start - 1;
+        let mut length = 1;
+        let mut block_size = self.base_at(max_length - 1);
+        while offset >= block_size && length < max_length {
+            offset -= block_size;
+            length += 1;
+            block_size *= self.base_at(max_length - length);
+        }
+        let active_start = max_length - length;
+        let mut digits = vec![0u128; length];
+        for position in (active_start..max_length).rev() {
+            let base = self.base_at(position);
+            digits[position - active_start] = offset % base;
+            offset /= base;
+        }
+        self.current_combination = String::with_capacity(max_length);
+        self.generators = Vec::with_capacity(max_length);
+        // Positions to the left of the recovered length are still untouched, as they would be
+        // at the start of a fresh `new()` run, and only get activated by a later carry.
+        for position in 0..active_start {
+            self.generators.push(self.alphabets[position].chars());
+        }
+        for (position, digit) in (active_start..max_length).zip(digits) {
+            let mut chars = self.alphabets[position].chars();
+            let selected_char = chars.nth(digit as usize).expect("digit within alphabet bounds");
+            self.current_combination.push(selected_char);
+            self.generators.push(chars);
+        }
+        Ok(())
     }
 
     #[inline]
@@ -68,8 +366,10 @@ impl<'a> StringsGenerator<'a> {
                 return Err(());
             }
             while tries != 0 {
-                // Recover char length removed by failed increment before of carry
-                let mut new_chars = self.dictionary.chars();
+                // Recover char length removed by failed increment before of carry, drawing
+                // from the alphabet of the position being reinitialized
+                let position = self.generators.len();
+                let mut new_chars = self.alphabets[position].chars();
                 self.current_combination.push(new_chars.next().unwrap());
                 self.generators.push(new_chars);
                 tries -= 1;
@@ -88,8 +388,10 @@ impl<'a> StringsGenerator<'a> {
                 }
                 // This increment the current last char before to initialize previous last again to do carry increment
                 self.increment()?;
-                // Recover char length removed by failed increment before of carry
-                let mut new_chars = self.dictionary_source.chars();
+                // Recover char length removed by failed increment before of carry, drawing
+                // from the alphabet of the position being reinitialized
+                let position = self.generators.len();
+                let mut new_chars = self.alphabets[position].chars();
                 self.current_combination.push(new_chars.next().unwrap());
                 self.generators.push(new_chars);
             }
@@ -120,8 +422,82 @@ struct CliOpts {
     max_length: u8,
     #[structopt(short, long, default_value = " -> ")]
     separator: String,
+    /// Dictionary attack mode: stream candidate usernames line-by-line from a file
+    /// instead of generating them combinatorially. Mutually exclusive with `--max-length`.
+    #[structopt(short, long, parse(from_os_str), conflicts_with = "max-length")]
+    wordlist: Option<std::path::PathBuf>,
+    /// Hash algorithm used to interpret `--hashed-emails` (sha256, sha512, sha1, md5, sha256d).
+    #[structopt(short, long, default_value = "sha256")]
+    algorithm: HashKind,
+    /// Resume (or distribute) combinatorial generation from this global candidate index
+    /// instead of index 0. Not compatible with `--wordlist`.
+    ///
+    /// No `default_value` here: combining one with `conflicts_with` would make clap treat
+    /// this as always present, rejecting `--wordlist` even when `--start` was never passed.
+    /// Mutual exclusion with `--wordlist` is instead checked by hand in `main`.
+    #[structopt(long)]
+    start: Option<u64>,
+    /// Stop after trying this many candidates from `--start`. Not compatible with `--wordlist`.
+    #[structopt(long)]
+    count: Option<u64>,
+    /// Periodically persist the next untried candidate index to this file, so an
+    /// interrupted run can be resumed by passing that value to `--start`.
+    /// Not compatible with `--wordlist`.
+    #[structopt(long, parse(from_os_str), conflicts_with = "wordlist")]
+    checkpoint: Option<std::path::PathBuf>,
+    /// Match candidates whose computed digest merely starts with this hex prefix, instead of
+    /// requiring full equality against `--hashed-emails`. Useful when only a truncated hash
+    /// fragment is known.
+    #[structopt(long, conflicts_with = "leading-zeros")]
+    prefix: Option<String>,
+    /// Match candidates whose computed digest has at least this many leading zero hex
+    /// nibbles, instead of requiring full equality against `--hashed-emails`. Enables
+    /// proof-of-work-style "most leading zeros" searches over the email space.
+    #[structopt(long, conflicts_with = "prefix")]
+    leading_zeros: Option<usize>,
+    /// Normalize each generated email before hashing. Repeatable or comma-separated;
+    /// supported rules: `lowercase`, `trim`, `gmail-dots`, `plus-tags`.
+    #[structopt(long, use_delimiter = true)]
+    normalize: Vec<NormalizeRule>,
+    /// Hashcat-style per-position character mask (e.g. `?l?l?d?d` for two lowercase letters
+    /// followed by two digits, or explicit bracketed sets like `[ABC][01]`), replacing the
+    /// single global dictionary with one alphabet per position. Mutually exclusive with
+    /// `--max-length` and `--wordlist`.
+    #[structopt(long, conflicts_with_all = &["max-length", "wordlist"])]
+    mask: Option<String>,
+}
+
+/// Stream lines out of a wordlist reader, skipping (and logging to stderr) any unreadable
+/// line (e.g. invalid UTF-8) instead of abandoning the rest of the file. Real-world leaked
+/// credential dumps routinely contain a handful of non-UTF-8 lines; stopping at the first one
+/// would silently throw away everything after it in a multi-GB wordlist.
+fn wordlist_lines<R: BufRead>(reader: R) -> impl Iterator<Item = String> {
+    reader.lines().filter_map(|line| {
+        line.map_err(|error| eprintln!("Skipping unreadable wordlist line: {}", error)).ok()
+    })
 }
 
+/// Decide whether a candidate's computed digest is a match, under whichever of the three
+/// mutually exclusive matching modes is active: `--prefix`, `--leading-zeros`, or (the
+/// default) full equality against `--hashed-emails`.
+fn matches_target(
+    email_hash_hex: &str,
+    prefix: Option<&str>,
+    leading_zeros: Option<usize>,
+    hashed_emails: &[String],
+) -> bool {
+    if let Some(prefix) = prefix {
+        return email_hash_hex.starts_with(prefix);
+    }
+    if let Some(leading_zeros) = leading_zeros {
+        return email_hash_hex.chars().take_while(|hex_char| *hex_char == '0').count() >= leading_zeros;
+    }
+    hashed_emails.par_iter().any(|looking_hash| email_hash_hex == looking_hash)
+}
+
+/// How many candidates to try between checkpoint file writes.
+const CHECKPOINT_EVERY: u64 = 5_000_000;
+
 const DICTIONARY: &'static str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789_.+";
 
 fn main() {
@@ -131,32 +507,239 @@ fn main() {
         if options.max_length > 64 {
             options.max_length = 64;
         }
+        // hex::encode always emits lowercase, so a mixed/uppercase --prefix (e.g. pasted from
+        // a tool that displays hex uppercase) would otherwise silently match nothing.
+        options.prefix = options.prefix.map(|prefix| prefix.to_lowercase());
+        // `start`/`count` can't carry both a `default_value` and `conflicts_with = "wordlist"`
+        // (clap would then treat them as always present and reject every `--wordlist` run), so
+        // the mutual exclusion with `--wordlist` is checked by hand here instead.
+        if options.wordlist.is_some() && (options.start.is_some() || options.count.is_some()) {
+            panic!("The argument '--wordlist' cannot be used with '--start' or '--count'");
+        }
         options
     };
     let stdout = std::io::stdout();
+    let mask_alphabets = options.mask.as_ref().map(|mask| {
+        parse_mask(mask).unwrap_or_else(|error| panic!("Invalid mask: {}", error))
+    });
+    // Either stream candidate usernames from a wordlist or generate them combinatorially
+    let usernames: Box<dyn Iterator<Item = String> + Send + '_> = if let Some(path) = &options.wordlist {
+        let file = std::fs::File::open(path)
+            .unwrap_or_else(|error| panic!("Can NOT open wordlist file: {}", error));
+        // Buffered reader bridged into par_bridge so huge wordlists are read lazily.
+        Box::new(wordlist_lines(BufReader::new(file)))
+    } else {
+        let start = options.start.unwrap_or(0);
+        let count = options.count.unwrap_or(0);
+        let generator = match &mask_alphabets {
+            Some(alphabets) => {
+                let alphabets: Vec<&str> = alphabets.iter().map(String::as_str).collect();
+                // `start` must seek within the mask's full-length block, not from index 0 of
+                // the unrestricted (shorter-length-included) enumeration, so it's folded into
+                // new_exact_length's own seek rather than applied again below.
+                StringsGenerator::new_exact_length(alphabets, start)
+            }
+            None => {
+                let alphabets = vec![DICTIONARY; options.max_length.into()];
+                let mut generator = StringsGenerator::new(alphabets);
+                if start > 0 {
+                    generator
+                        .seek(start)
+                        .unwrap_or_else(|error| panic!("Invalid --start: {}", error));
+                }
+                generator
+            }
+        };
+        let generator: Box<dyn Iterator<Item = String> + Send + '_> = if count > 0 {
+            Box::new(generator.take(count as usize))
+        } else {
+            Box::new(generator)
+        };
+        let mut completed = start;
+        let checkpoint_path = options.checkpoint.clone();
+        Box::new(generator.inspect(move |_username| {
+            completed += 1;
+            if let Some(path) = &checkpoint_path {
+                if completed % CHECKPOINT_EVERY == 0 {
+                    let _ = std::fs::write(path, completed.to_string());
+                }
+            }
+        }))
+    };
     // Parallel iterators to process all since generate usernames until get hashes and compare
-    StringsGenerator::new(options.max_length.into(), DICTIONARY)
+    usernames
     .par_bridge()
     .flat_map(|username| {
         options.domains.par_iter().map(move |domain| {
             format!("{}@{}", username, domain)
         })
     })
+    .map(|email| normalize_email(email, &options.normalize))
     .map(|email| {
-        let mut hasher = Sha256::new();
-        hasher.update(&email);
-        let email_sha256_hex = hex::encode(hasher.finalize());
-        (email, email_sha256_hex)
+        let email_hash_hex = options.algorithm.hash_hex(email.as_bytes());
+        (email, email_hash_hex)
     })
-    .filter(|(_email, email_sha256_hex)| {
-        options.hashed_emails.par_iter().any(|looking_hash| {
-            email_sha256_hex == looking_hash
-        })
+    .filter(|(_email, email_hash_hex)| {
+        matches_target(
+            email_hash_hex,
+            options.prefix.as_deref(),
+            options.leading_zeros,
+            &options.hashed_emails,
+        )
     })
-    .for_each(|(email, email_sha256_hex)| {
+    .for_each(|(email, email_hash_hex)| {
         let mut handle = stdout.lock();
-        if writeln!(handle, "{}{}{}", email, options.separator, email_sha256_hex).is_err() {
+        if writeln!(handle, "{}{}{}", email, options.separator, email_hash_hex).is_err() {
             panic!("Can NOT write result to stdout");
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Independent oracle for `seek`: walks the enumeration one candidate at a time from the
+    /// start, so it doesn't share any of `seek`'s mixed-radix arithmetic.
+    fn brute_force_nth(alphabets: Vec<&str>, index: u64) -> String {
+        let mut generator = StringsGenerator::new(alphabets);
+        let mut candidate = None;
+        for _ in 0..=index {
+            candidate = generator.next();
+        }
+        candidate.expect("index within enumeration")
+    }
+
+    #[test]
+    fn seek_matches_brute_force_enumeration() {
+        let alphabets = vec!["ab", "ab", "ab"];
+        let total = 2 + 4 + 8; // B + B^2 + B^3 for B = 2, max_length = 3
+        for index in 0..total {
+            let mut generator = StringsGenerator::new(alphabets.clone());
+            generator.seek(index).expect("index within range");
+            let seeked = generator.next().expect("seek should produce a candidate");
+            assert_eq!(seeked, brute_force_nth(alphabets.clone(), index));
+        }
+    }
+
+    #[test]
+    fn seek_rejects_start_past_the_end() {
+        let alphabets = vec!["ab", "ab", "ab"];
+        let total = 2 + 4 + 8;
+        assert!(StringsGenerator::new(alphabets.clone()).seek(total).is_err());
+        assert!(StringsGenerator::new(alphabets).seek(total + 1).is_err());
+    }
+
+    #[test]
+    fn seek_handles_realistic_default_scale_without_overflow() {
+        // The default 65-char dictionary at the default `--max-length 64` is astronomically
+        // large; a perfectly plausible distributed-work `--start` like this one used to
+        // overflow `u64` arithmetic inside `seek` (panicking in debug, wrapping in release).
+        let alphabets = vec![DICTIONARY; 64];
+        let mut generator = StringsGenerator::new(alphabets);
+        generator
+            .seek(5_000_000_000_000_000_000)
+            .expect("start within the default space");
+        assert!(generator.next().is_some());
+    }
+
+    #[test]
+    fn new_exact_length_only_yields_the_mask_s_full_length() {
+        let alphabets = parse_mask("?l?d").unwrap();
+        let alphabets: Vec<&str> = alphabets.iter().map(String::as_str).collect();
+        let mut generator = StringsGenerator::new_exact_length(alphabets, 0);
+        // 26 lowercase letters * 10 digits candidates, every one exactly 2 chars long, the
+        // first drawn from `?l` and the second from `?d` -- never the reverse, which is what
+        // the shorter-length phase of a plain `new()` used to silently produce.
+        let candidates: Vec<String> = (&mut generator).take(26 * 10).collect();
+        assert_eq!(candidates.len(), 26 * 10);
+        for candidate in &candidates {
+            assert_eq!(candidate.len(), 2);
+            assert!(candidate.as_bytes()[0].is_ascii_lowercase());
+            assert!(candidate.as_bytes()[1].is_ascii_digit());
+        }
+        assert!(generator.next().is_none(), "mask space should be exhausted");
+    }
+
+    #[test]
+    fn new_exact_length_with_start_resumes_inside_the_full_length_block() {
+        let alphabets = parse_mask("?l?d").unwrap();
+        let alphabets: Vec<&str> = alphabets.iter().map(String::as_str).collect();
+        // --start 1 should resume from the full-length block's 2nd candidate, not re-seek
+        // into the shorter-length (single-char) phase of the unrestricted enumeration.
+        let mut generator = StringsGenerator::new_exact_length(alphabets, 1);
+        let candidate = generator.next().expect("mask space is not exhausted");
+        assert_eq!(candidate.len(), 2);
+        assert_eq!(candidate.as_bytes()[0], b'a');
+        assert_eq!(candidate.as_bytes()[1], b'1');
+    }
+
+    #[test]
+    fn hash_hex_matches_each_algorithm() {
+        assert_eq!(
+            HashKind::Sha256.hash_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            HashKind::Sha1.hash_hex(b"abc"),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+        assert_eq!(
+            HashKind::Md5.hash_hex(b"abc"),
+            "900150983cd24fb0d6963f7d28e17f72"
+        );
+        // sha256d is just sha256 applied twice; check it agrees with doing that by hand.
+        let once = HashKind::Sha256.hash_hex(b"abc");
+        let twice = HashKind::Sha256.hash_hex(hex::decode(&once).unwrap().as_slice());
+        assert_eq!(HashKind::Sha256d.hash_hex(b"abc"), twice);
+    }
+
+    #[test]
+    fn normalize_email_applies_rules_in_order() {
+        let rules = [
+            NormalizeRule::Trim,
+            NormalizeRule::Lowercase,
+            NormalizeRule::GmailDots,
+            NormalizeRule::PlusTags,
+        ];
+        assert_eq!(
+            normalize_email("  John.Doe+promo@Gmail.com  ".to_string(), &rules),
+            "johndoe@gmail.com"
+        );
+        // Without gmail-dots/plus-tags, the local part is left alone.
+        assert_eq!(
+            normalize_email(" A@B.com ".to_string(), &[NormalizeRule::Trim]),
+            "A@B.com"
+        );
+    }
+
+    #[test]
+    fn parse_mask_builds_one_alphabet_per_position() {
+        let alphabets = parse_mask("?lX[01]").unwrap();
+        assert_eq!(alphabets, vec!["abcdefghijklmnopqrstuvwxyz", "X", "01"]);
+        assert!(parse_mask("").is_err());
+        assert!(parse_mask("?").is_err());
+        assert!(parse_mask("[ab").is_err());
+    }
+
+    #[test]
+    fn wordlist_lines_skips_unreadable_lines_and_keeps_going() {
+        // 0x9F is not valid UTF-8 on its own, so the second "line" fails to parse.
+        let mut bytes = b"first\n".to_vec();
+        bytes.extend_from_slice(&[0x9F, b'\n']);
+        bytes.extend_from_slice(b"third\n");
+        let lines: Vec<String> = wordlist_lines(std::io::Cursor::new(bytes)).collect();
+        assert_eq!(lines, vec!["first".to_string(), "third".to_string()]);
+    }
+
+    #[test]
+    fn matches_target_modes_are_mutually_exclusive_in_priority() {
+        let hashed_emails = vec!["deadbeef".to_string()];
+        assert!(matches_target("deadbeef", None, None, &hashed_emails));
+        assert!(!matches_target("deadbeee", None, None, &hashed_emails));
+        assert!(matches_target("dead1234", Some("dead"), None, &hashed_emails));
+        assert!(!matches_target("beef1234", Some("dead"), None, &hashed_emails));
+        assert!(matches_target("00ab", None, Some(2), &hashed_emails));
+        assert!(!matches_target("0ab", None, Some(2), &hashed_emails));
+    }
+}